@@ -0,0 +1,86 @@
+#![no_main]
+
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::Digest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as Sha2Digest, Sha256};
+
+// Only the field the aggregator actually folds into the Merkle tree is modeled here
+#[derive(Deserialize, Debug)]
+struct ChildJournal {
+    #[serde(rename = "outputHash")]
+    output_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AggregateJournal {
+    #[serde(rename = "programHash")]
+    program_hash: String,
+    #[serde(rename = "merkleRoot")]
+    merkle_root: String,
+    count: u32,
+}
+
+fn canonical_json<T: Serialize>(value: &T) -> String {
+    // Compact JSON with fields in struct declaration order and no extra whitespace
+    serde_json::to_string(value).unwrap()
+}
+
+// Pairwise SHA-256 Merkle root, duplicating the last leaf on odd counts
+fn merkle_root(mut leaves: Vec<[u8; 32]>) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    while leaves.len() > 1 {
+        if leaves.len() % 2 == 1 {
+            leaves.push(*leaves.last().unwrap());
+        }
+        leaves = leaves
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Sha256::new();
+                hasher.update(pair[0]);
+                hasher.update(pair[1]);
+                let mut out = [0u8; 32];
+                out.copy_from_slice(&hasher.finalize());
+                out
+            })
+            .collect();
+    }
+    leaves[0]
+}
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    // Every child document was summarized by the same guest, so one image ID applies to all
+    let child_image_id: Digest = env::read();
+    let child_journals: Vec<String> = env::read();
+
+    let mut leaves = Vec::with_capacity(child_journals.len());
+    for journal_str in &child_journals {
+        // Fold the child's proof into this proof: it must verify against the shared image ID
+        env::verify(child_image_id, journal_str.as_bytes()).unwrap();
+
+        let child: ChildJournal =
+            serde_json::from_str(journal_str).expect("child journal should be valid JSON");
+        let hash_hex = child
+            .output_hash
+            .strip_prefix("sha256:")
+            .expect("outputHash should be sha256-prefixed");
+        let mut leaf = [0u8; 32];
+        hex::decode_to_slice(hash_hex, &mut leaf).expect("outputHash should be a 32-byte hex digest");
+        leaves.push(leaf);
+    }
+
+    let root = merkle_root(leaves);
+
+    let journal = AggregateJournal {
+        program_hash: child_image_id.to_string(),
+        merkle_root: format!("sha256:{}", hex::encode(root)),
+        count: child_journals.len() as u32,
+    };
+
+    let journal_json = canonical_json(&journal);
+    env::commit_slice(journal_json.as_bytes());
+}
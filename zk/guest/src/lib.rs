@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+// Embedded stopwords list for deterministic filtering
+const STOPWORDS: &str = include_str!("stopwords.txt");
+
+const TOP_K: usize = 5;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Keyword {
+    pub word: String,
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Journal {
+    #[serde(rename = "programHash")]
+    pub program_hash: String,
+    #[serde(rename = "inputHash")]
+    pub input_hash: String,
+    #[serde(rename = "outputHash")]
+    pub output_hash: String,
+    pub keywords: Vec<Keyword>,
+}
+
+pub fn normalize_text(input: &str) -> Vec<String> {
+    // Load stopwords into a set for fast lookup
+    let stopwords: std::collections::HashSet<&str> = STOPWORDS
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    // Normalize: lowercase, split on any char that is not an ASCII letter, filter stopwords and empty
+    input
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty() && !stopwords.contains(word))
+        .map(|word| word.to_string())
+        .collect()
+}
+
+pub fn canonical_json<T: Serialize>(value: &T) -> String {
+    // Compact JSON with fields in struct declaration order and no extra whitespace
+    serde_json::to_string(value).unwrap()
+}
+
+pub fn top_keywords(input: &str) -> Vec<Keyword> {
+    let words = normalize_text(input);
+    let mut word_counts = HashMap::new();
+    for word in words {
+        *word_counts.entry(word).or_insert(0u32) += 1;
+    }
+
+    // Sort deterministically: by (-count, word) for stable ordering
+    let mut keywords_vec: Vec<_> = word_counts.into_iter().collect();
+    keywords_vec.sort_by(|a, b| {
+        // Sort by count descending, then by word ascending
+        b.1.cmp(&a.1).then(a.0.cmp(&b.0))
+    });
+
+    keywords_vec
+        .into_iter()
+        .take(TOP_K)
+        .map(|(word, count)| Keyword { word, count })
+        .collect()
+}
+
+pub fn build_journal(input_bytes: &[u8], program_hash: &str) -> Journal {
+    let input = String::from_utf8(input_bytes.to_vec()).expect("Invalid UTF-8 input");
+    let keywords = top_keywords(&input);
+
+    let input_hash = format!("sha256:{}", hex::encode(Sha256::digest(input_bytes)));
+    let keywords_canonical = canonical_json(&keywords);
+    let output_hash = format!(
+        "sha256:{}",
+        hex::encode(Sha256::digest(keywords_canonical.as_bytes()))
+    );
+
+    Journal {
+        program_hash: program_hash.to_string(),
+        input_hash,
+        output_hash,
+        keywords,
+    }
+}
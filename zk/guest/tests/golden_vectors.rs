@@ -0,0 +1,72 @@
+//! Pins down `normalize_text` and the top-K ranking against committed golden vectors.
+//! Any change to stopwords or tokenization that would alter committed hashes must
+//! update these vectors deliberately, not as an accidental side effect.
+
+use guest::{build_journal, Keyword};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+
+#[derive(Deserialize)]
+struct ExpectedKeyword {
+    word: String,
+    count: u32,
+}
+
+#[derive(Deserialize)]
+struct GoldenVector {
+    input: String,
+    expected_keywords: Vec<ExpectedKeyword>,
+    expected_output_hash: String,
+}
+
+#[test]
+fn golden_vectors_match() {
+    let pattern = concat!(env!("CARGO_MANIFEST_DIR"), "/testvectors/*.yaml");
+    let mut checked = 0;
+
+    for entry in glob::glob(pattern).expect("valid glob pattern") {
+        let path = entry.expect("readable testvectors entry");
+        let raw = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+        let vector: GoldenVector = serde_yaml::from_str(&raw)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {}", path.display(), e));
+
+        let journal = build_journal(vector.input.as_bytes(), "<FILLED_BY_HOST>");
+
+        let expected_keywords: Vec<Keyword> = vector
+            .expected_keywords
+            .iter()
+            .map(|k| Keyword {
+                word: k.word.clone(),
+                count: k.count,
+            })
+            .collect();
+        assert_eq!(
+            journal.keywords,
+            expected_keywords,
+            "keyword mismatch in {}",
+            path.display()
+        );
+
+        let keywords_canonical = serde_json::to_string(&journal.keywords).unwrap();
+        let recomputed_output_hash = format!(
+            "sha256:{}",
+            hex::encode(Sha256::digest(keywords_canonical.as_bytes()))
+        );
+        assert_eq!(
+            recomputed_output_hash, vector.expected_output_hash,
+            "recomputed outputHash mismatch in {}",
+            path.display()
+        );
+        assert_eq!(
+            journal.output_hash, vector.expected_output_hash,
+            "journal outputHash mismatch in {}",
+            path.display()
+        );
+
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no golden vectors found under testvectors/");
+}
@@ -0,0 +1,69 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default content-addressable cache location: `~/.cache/zkhost/<hash-prefix>/<hash>`.
+pub fn default_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("zkhost")
+}
+
+pub fn input_hash_hex(input_bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(input_bytes))
+}
+
+pub struct CacheEntry {
+    pub receipt_path: PathBuf,
+    pub journal_path: PathBuf,
+}
+
+fn entry_dir(cache_dir: &Path, hash_hex: &str) -> PathBuf {
+    cache_dir.join(&hash_hex[..2]).join(hash_hex)
+}
+
+fn index_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("index.json")
+}
+
+fn load_index(cache_dir: &Path) -> HashSet<String> {
+    fs::read_to_string(index_path(cache_dir))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Looks up a cache entry by input hash. Returns `None` on a miss or a partially written entry.
+pub fn lookup(cache_dir: &Path, hash_hex: &str) -> Option<CacheEntry> {
+    let dir = entry_dir(cache_dir, hash_hex);
+    let receipt_path = dir.join("receipt.bin");
+    let journal_path = dir.join("journal.json");
+    if receipt_path.is_file() && journal_path.is_file() {
+        Some(CacheEntry {
+            receipt_path,
+            journal_path,
+        })
+    } else {
+        None
+    }
+}
+
+/// Writes a cache entry and dedupes it into the hash-keyed index.
+pub fn store(
+    cache_dir: &Path,
+    hash_hex: &str,
+    receipt_bytes: &[u8],
+    journal_json: &str,
+) -> io::Result<()> {
+    let dir = entry_dir(cache_dir, hash_hex);
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("receipt.bin"), receipt_bytes)?;
+    fs::write(dir.join("journal.json"), journal_json)?;
+
+    let mut index = load_index(cache_dir);
+    index.insert(hash_hex.to_string());
+    let index_json = serde_json::to_string_pretty(&index).unwrap();
+    fs::write(index_path(cache_dir), index_json)
+}
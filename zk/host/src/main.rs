@@ -1,43 +1,221 @@
+use alloy_sol_types::{sol, SolValue};
 use clap::{Arg, Command};
-use risc0_zkvm::{default_prover, ExecutorEnv};
+use jsonwebtoken::Algorithm;
+use risc0_ethereum_contracts::encode_seal;
+use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, Receipt};
+use serde::{Deserialize, Serialize};
 use serde_json;
+use sha2::{Digest as Sha2Digest, Sha256};
 use std::fs;
 use std::path::Path;
 use std::process;
 
 // Include the guest methods
 mod methods;
-use methods::{GUEST_ELF, guest_id};
+use methods::{aggregator_id, guest_id, AGGREGATOR_ELF, GUEST_ELF};
+
+mod cache;
+mod vc;
+
+sol! {
+    /// On-chain payload handed to a Solidity verifier contract.
+    struct JournalSeal {
+        bytes journal;
+        bytes seal;
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Keyword {
+    word: String,
+    count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Journal {
+    #[serde(rename = "programHash")]
+    program_hash: String,
+    #[serde(rename = "inputHash")]
+    input_hash: String,
+    #[serde(rename = "outputHash")]
+    output_hash: String,
+    keywords: Vec<Keyword>,
+}
 
 fn main() {
     let matches = Command::new("zkhost")
         .about("RISC Zero host runner for deterministic summarization")
-        .arg(
-            Arg::new("input")
-                .long("in")
-                .value_name("FILE")
-                .help("Input text file")
-                .required(true),
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("prove")
+                .about("Generate a ZK proof over an input file")
+                .arg(
+                    Arg::new("input")
+                        .long("in")
+                        .value_name("FILE")
+                        .help("Input text file")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("output")
+                        .long("out")
+                        .value_name("FILE")
+                        .help("Output journal JSON file")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("proof")
+                        .long("proof")
+                        .value_name("FILE")
+                        .help("Output proof file (serialized Receipt, or hex of the ABI-encoded JournalSeal with --groth16)")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("groth16")
+                        .long("groth16")
+                        .help("Produce a compressed Groth16 SNARK receipt, ABI-encoded for on-chain verification")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("no-cache")
+                        .long("no-cache")
+                        .help("Skip the content-addressable proof cache")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("cache-dir")
+                        .long("cache-dir")
+                        .value_name("DIR")
+                        .help("Proof cache directory (default: ~/.cache/zkhost)"),
+                ),
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("Independently verify a receipt against an expected journal")
+                .arg(
+                    Arg::new("proof")
+                        .long("proof")
+                        .value_name("FILE")
+                        .help("Serialized Receipt file to verify")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("journal")
+                        .long("journal")
+                        .value_name("FILE")
+                        .help("Expected journal JSON file")
+                        .required(true),
+                ),
         )
-        .arg(
-            Arg::new("output")
-                .long("out")
-                .value_name("FILE")
-                .help("Output journal JSON file")
-                .required(true),
+        .subcommand(
+            Command::new("batch")
+                .about("Prove every file in a directory and aggregate the proofs into one receipt")
+                .arg(
+                    Arg::new("dir")
+                        .long("dir")
+                        .value_name("DIR")
+                        .help("Directory of input text files")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("output")
+                        .long("out")
+                        .value_name("FILE")
+                        .help("Output aggregate journal JSON file")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("proof")
+                        .long("proof")
+                        .value_name("FILE")
+                        .help("Output aggregate proof file (serialized Receipt)")
+                        .required(true),
+                ),
         )
-        .arg(
-            Arg::new("proof")
-                .long("proof")
-                .value_name("FILE")
-                .help("Output proof binary file")
-                .required(true),
+        .subcommand(
+            Command::new("credential")
+                .about("Wrap a committed journal as a signed W3C Verifiable Credential (JWT)")
+                .arg(
+                    Arg::new("journal")
+                        .long("journal")
+                        .value_name("FILE")
+                        .help("Journal JSON file to wrap")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("key")
+                        .long("key")
+                        .value_name("FILE")
+                        .help("PEM-encoded signing key")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("issuer")
+                        .long("issuer")
+                        .value_name("DID_OR_URI")
+                        .help("Credential issuer identifier")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("alg")
+                        .long("alg")
+                        .value_name("ES256|RS256")
+                        .help("Signing algorithm")
+                        .default_value("ES256"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .long("out")
+                        .value_name("FILE")
+                        .help("Output compact JWS file")
+                        .required(true),
+                ),
         )
         .get_matches();
 
+    match matches.subcommand() {
+        Some(("prove", sub_matches)) => cmd_prove(sub_matches),
+        Some(("verify", sub_matches)) => cmd_verify(sub_matches),
+        Some(("batch", sub_matches)) => cmd_batch(sub_matches),
+        Some(("credential", sub_matches)) => cmd_credential(sub_matches),
+        _ => unreachable!("subcommand_required guarantees a match"),
+    }
+}
+
+fn load_cached_receipt(receipt_path: &Path) -> Option<Receipt> {
+    let bytes = fs::read(receipt_path).ok()?;
+    bincode::deserialize(&bytes).ok()
+}
+
+fn copy_cache_entry_to_outputs(entry: &cache::CacheEntry, output_file: &str, proof_file: &str) {
+    if let Some(parent) = Path::new(output_file).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Some(parent) = Path::new(proof_file).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Err(e) = fs::copy(&entry.journal_path, output_file) {
+        eprintln!("Error writing journal file '{}': {}", output_file, e);
+        process::exit(1);
+    }
+    if let Err(e) = fs::copy(&entry.receipt_path, proof_file) {
+        eprintln!("Error writing proof file '{}': {}", proof_file, e);
+        process::exit(1);
+    }
+    println!("📄 Journal: {}", output_file);
+    println!("🔒 Proof: {}", proof_file);
+}
+
+fn cmd_prove(matches: &clap::ArgMatches) {
     let input_file = matches.get_one::<String>("input").unwrap();
     let output_file = matches.get_one::<String>("output").unwrap();
     let proof_file = matches.get_one::<String>("proof").unwrap();
+    let groth16 = matches.get_flag("groth16");
+    let no_cache = matches.get_flag("no-cache");
+    let cache_dir = matches
+        .get_one::<String>("cache-dir")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(cache::default_cache_dir);
 
     // Read input file bytes
     let input_bytes = match fs::read(input_file) {
@@ -48,27 +226,51 @@ fn main() {
         }
     };
 
-    // Create execution environment with input data
+    // Groth16 output is never cached: the content-addressable cache only covers the
+    // default (non-groth16) path.
+    if !groth16 && !no_cache {
+        let input_hash = cache::input_hash_hex(&input_bytes);
+        if let Some(entry) = cache::lookup(&cache_dir, &input_hash) {
+            if let Some(receipt) = load_cached_receipt(&entry.receipt_path) {
+                if receipt.verify(guest_id()).is_ok() {
+                    println!("✅ Cache hit for inputHash sha256:{} — skipping proving", input_hash);
+                    copy_cache_entry_to_outputs(&entry, output_file, proof_file);
+                    return;
+                }
+                eprintln!("⚠️  Cached receipt for inputHash sha256:{} failed verification, re-proving", input_hash);
+            }
+        }
+    }
+
+    // Create execution environment with input data. The guest can't compute its own image
+    // ID from inside the zkVM, so the host passes it in to be committed as programHash.
+    let program_hash = guest_id().to_string();
     let env = ExecutorEnv::builder()
+        .write(&program_hash)
+        .unwrap()
         .write(&input_bytes)
         .unwrap()
         .build()
         .unwrap();
-    
+
     // Generate the proof using real prover (not dev mode)
     println!("🔄 Generating ZK proof...");
     let prover = default_prover();
-    let prove_info = prover
-        .prove(env, GUEST_ELF)
-        .unwrap();
-    
+    let prove_info = if groth16 {
+        prover
+            .prove_with_opts(env, GUEST_ELF, &ProverOpts::groth16())
+            .unwrap()
+    } else {
+        prover.prove(env, GUEST_ELF).unwrap()
+    };
+
     // Extract journal data from the receipt
     let journal_bytes = prove_info.journal.bytes.clone();
-    let journal_str = String::from_utf8(journal_bytes)
-        .expect("Journal should contain valid UTF-8");
-    let journal_data: serde_json::Value = serde_json::from_str(&journal_str)
-        .expect("Journal should contain valid JSON");
-    
+    let journal_str =
+        String::from_utf8(journal_bytes).expect("Journal should contain valid UTF-8");
+    let journal_data: serde_json::Value =
+        serde_json::from_str(&journal_str).expect("Journal should contain valid JSON");
+
     // Create output directory if it doesn't exist
     if let Some(parent) = Path::new(output_file).parent() {
         if let Err(e) = fs::create_dir_all(parent) {
@@ -82,28 +284,320 @@ fn main() {
             process::exit(1);
         }
     }
-    
+
     // Write journal data
     let journal_json = serde_json::to_string_pretty(&journal_data).unwrap();
     let journal_json_len = journal_json.len();
-    
-    if let Err(e) = fs::write(&output_file, journal_json) {
+
+    if let Err(e) = fs::write(&output_file, &journal_json) {
         eprintln!("Error writing journal file '{}': {}", output_file, e);
         process::exit(1);
     }
-    
-    // Write the seal as proof (this is the actual ZK proof)
-    let proof_bytes = prove_info.inner.seal().to_vec();
-    if let Err(e) = fs::write(&proof_file, &proof_bytes) {
-        eprintln!("Error writing proof file '{}': {}", proof_file, e);
-        process::exit(1);
-    }
-    
+
+    let proof_byte_len = if groth16 {
+        // Compressed SNARK: ABI-encode (journal, seal) for a Solidity verifier contract
+        let seal = encode_seal(&prove_info).unwrap();
+        let journal_seal = JournalSeal {
+            journal: prove_info.journal.bytes.clone().into(),
+            seal: seal.into(),
+        };
+        let abi_hex = hex::encode(journal_seal.abi_encode());
+        if let Err(e) = fs::write(&proof_file, &abi_hex) {
+            eprintln!("Error writing proof file '{}': {}", proof_file, e);
+            process::exit(1);
+        }
+        abi_hex.len()
+    } else {
+        // Write the full receipt (not just the seal) so it can be independently verified later
+        let receipt_bytes = bincode::serialize(&prove_info).unwrap();
+        if let Err(e) = fs::write(&proof_file, &receipt_bytes) {
+            eprintln!("Error writing proof file '{}': {}", proof_file, e);
+            process::exit(1);
+        }
+        if !no_cache {
+            let input_hash = cache::input_hash_hex(&input_bytes);
+            if let Err(e) = cache::store(&cache_dir, &input_hash, &receipt_bytes, &journal_json) {
+                eprintln!("⚠️  Failed to write cache entry for inputHash sha256:{}: {}", input_hash, e);
+            }
+        }
+        receipt_bytes.len()
+    };
+
     println!("✅ ZK proof generated successfully!");
     println!("📄 Journal: {} ({} bytes)", output_file, journal_json_len);
-    println!("🔒 Proof: {} ({} bytes)", proof_file, proof_bytes.len());
-    
-    // Convert guest ID to hex string
+    println!("🔒 Proof: {} ({} bytes)", proof_file, proof_byte_len);
+
+    // Convert guest ID to hex string, and to the raw 32-byte digest Solidity verifiers expect
     let guest_id_digest = guest_id();
     println!("🎯 Image ID: {}", guest_id_digest);
-}
\ No newline at end of file
+    if groth16 {
+        println!(
+            "🔗 Image ID (bytes32): 0x{}",
+            hex::encode(guest_id_digest.as_bytes())
+        );
+    }
+}
+
+fn cmd_verify(matches: &clap::ArgMatches) {
+    let proof_file = matches.get_one::<String>("proof").unwrap();
+    let journal_file = matches.get_one::<String>("journal").unwrap();
+
+    let receipt_bytes = match fs::read(proof_file) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("Error reading proof file '{}': {}", proof_file, e);
+            process::exit(1);
+        }
+    };
+    let receipt: Receipt = match bincode::deserialize(&receipt_bytes) {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            eprintln!("Error decoding receipt '{}': {}", proof_file, e);
+            process::exit(1);
+        }
+    };
+
+    let expected_journal_str = match fs::read_to_string(journal_file) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error reading journal file '{}': {}", journal_file, e);
+            process::exit(1);
+        }
+    };
+    let expected_journal: Journal = match serde_json::from_str(&expected_journal_str) {
+        Ok(journal) => journal,
+        Err(e) => {
+            eprintln!("Error parsing journal file '{}': {}", journal_file, e);
+            process::exit(1);
+        }
+    };
+
+    // Cryptographically verify the receipt against the guest image ID
+    let guest_id_digest = guest_id();
+    if let Err(e) = receipt.verify(guest_id_digest) {
+        eprintln!("❌ Receipt verification failed: {}", e);
+        process::exit(1);
+    }
+
+    // Cross-check the committed journal matches what the caller expects
+    let committed_str = String::from_utf8(receipt.journal.bytes.clone())
+        .expect("Journal should contain valid UTF-8");
+    let committed_journal: Journal = serde_json::from_str(&committed_str)
+        .expect("Committed journal should contain valid JSON");
+
+    let expected_image_id = format!("{}", guest_id_digest);
+    if committed_journal.program_hash != expected_image_id {
+        eprintln!(
+            "❌ programHash mismatch: journal has '{}', expected '{}'",
+            committed_journal.program_hash, expected_image_id
+        );
+        process::exit(1);
+    }
+
+    if committed_journal.input_hash != expected_journal.input_hash {
+        eprintln!(
+            "❌ inputHash mismatch: journal has '{}', expected '{}'",
+            committed_journal.input_hash, expected_journal.input_hash
+        );
+        process::exit(1);
+    }
+
+    let keywords_canonical = serde_json::to_string(&committed_journal.keywords).unwrap();
+    let recomputed_output_hash = format!(
+        "sha256:{}",
+        hex::encode(Sha256::digest(keywords_canonical.as_bytes()))
+    );
+    if recomputed_output_hash != committed_journal.output_hash {
+        eprintln!(
+            "❌ outputHash does not match the committed keywords: recomputed '{}', committed '{}'",
+            recomputed_output_hash, committed_journal.output_hash
+        );
+        process::exit(1);
+    }
+    if committed_journal.output_hash != expected_journal.output_hash {
+        eprintln!(
+            "❌ outputHash mismatch: journal has '{}', expected '{}'",
+            committed_journal.output_hash, expected_journal.output_hash
+        );
+        process::exit(1);
+    }
+
+    println!("✅ Receipt verified against image ID {}", expected_image_id);
+    println!("✅ Journal matches: inputHash, outputHash and programHash all check out");
+}
+
+fn cmd_batch(matches: &clap::ArgMatches) {
+    let dir = matches.get_one::<String>("dir").unwrap();
+    let output_file = matches.get_one::<String>("output").unwrap();
+    let proof_file = matches.get_one::<String>("proof").unwrap();
+
+    let mut inputs: Vec<_> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .collect(),
+        Err(e) => {
+            eprintln!("Error reading input directory '{}': {}", dir, e);
+            process::exit(1);
+        }
+    };
+    inputs.sort();
+
+    if inputs.is_empty() {
+        eprintln!("No input files found in '{}'", dir);
+        process::exit(1);
+    }
+
+    // Prove each document independently with the existing summarization guest
+    println!("🔄 Proving {} documents...", inputs.len());
+    let prover = default_prover();
+    let mut child_receipts = Vec::with_capacity(inputs.len());
+    let mut child_journals = Vec::with_capacity(inputs.len());
+    let program_hash = guest_id().to_string();
+
+    for path in &inputs {
+        let input_bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                eprintln!("Error reading input file '{}': {}", path.display(), e);
+                process::exit(1);
+            }
+        };
+        let env = ExecutorEnv::builder()
+            .write(&program_hash)
+            .unwrap()
+            .write(&input_bytes)
+            .unwrap()
+            .build()
+            .unwrap();
+        let receipt = prover.prove(env, GUEST_ELF).unwrap();
+        let journal_str = String::from_utf8(receipt.journal.bytes.clone())
+            .expect("Journal should contain valid UTF-8");
+        println!("  ✓ {}", path.display());
+        child_journals.push(journal_str);
+        child_receipts.push(receipt);
+    }
+
+    // Feed each child receipt into the aggregation guest as an assumption, so it can
+    // recursively verify every document was summarized by the same guest image
+    let mut env_builder = ExecutorEnv::builder();
+    for receipt in &child_receipts {
+        env_builder.add_assumption(receipt.clone()).unwrap();
+    }
+    let agg_env = env_builder
+        .write(&guest_id())
+        .unwrap()
+        .write(&child_journals)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    println!("🔄 Aggregating {} proofs into one receipt...", inputs.len());
+    let agg_receipt = prover.prove(agg_env, AGGREGATOR_ELF).unwrap();
+
+    // Extract the combined journal
+    let journal_bytes = agg_receipt.journal.bytes.clone();
+    let journal_str =
+        String::from_utf8(journal_bytes).expect("Journal should contain valid UTF-8");
+    let journal_data: serde_json::Value =
+        serde_json::from_str(&journal_str).expect("Journal should contain valid JSON");
+
+    if let Some(parent) = Path::new(output_file).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Error creating output directory: {}", e);
+            process::exit(1);
+        }
+    }
+    if let Some(parent) = Path::new(proof_file).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Error creating proof directory: {}", e);
+            process::exit(1);
+        }
+    }
+
+    let journal_json = serde_json::to_string_pretty(&journal_data).unwrap();
+    if let Err(e) = fs::write(&output_file, &journal_json) {
+        eprintln!("Error writing journal file '{}': {}", output_file, e);
+        process::exit(1);
+    }
+
+    let receipt_bytes = bincode::serialize(&agg_receipt).unwrap();
+    if let Err(e) = fs::write(&proof_file, &receipt_bytes) {
+        eprintln!("Error writing proof file '{}': {}", proof_file, e);
+        process::exit(1);
+    }
+
+    println!("✅ Aggregate proof generated successfully!");
+    println!(
+        "📄 Journal: {} ({} documents, {} bytes)",
+        output_file,
+        inputs.len(),
+        journal_json.len()
+    );
+    println!("🔒 Proof: {} ({} bytes)", proof_file, receipt_bytes.len());
+    println!("🎯 Aggregator Image ID: {}", aggregator_id());
+}
+
+fn cmd_credential(matches: &clap::ArgMatches) {
+    let journal_file = matches.get_one::<String>("journal").unwrap();
+    let key_file = matches.get_one::<String>("key").unwrap();
+    let issuer = matches.get_one::<String>("issuer").unwrap();
+    let alg_name = matches.get_one::<String>("alg").unwrap();
+    let output_file = matches.get_one::<String>("output").unwrap();
+
+    let algorithm = match alg_name.as_str() {
+        "ES256" => Algorithm::ES256,
+        "RS256" => Algorithm::RS256,
+        other => {
+            eprintln!("Unsupported --alg '{}': expected ES256 or RS256", other);
+            process::exit(1);
+        }
+    };
+
+    let journal_str = match fs::read_to_string(journal_file) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error reading journal file '{}': {}", journal_file, e);
+            process::exit(1);
+        }
+    };
+    let journal: serde_json::Value = match serde_json::from_str(&journal_str) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error parsing journal file '{}': {}", journal_file, e);
+            process::exit(1);
+        }
+    };
+
+    let key_pem = match fs::read(key_file) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("Error reading signing key '{}': {}", key_file, e);
+            process::exit(1);
+        }
+    };
+
+    let jwt = match vc::sign_journal_as_vc(&journal, issuer, algorithm, &key_pem) {
+        Ok(jwt) => jwt,
+        Err(e) => {
+            eprintln!("Error signing credential: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Some(parent) = Path::new(output_file).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Error creating output directory: {}", e);
+            process::exit(1);
+        }
+    }
+    if let Err(e) = fs::write(&output_file, &jwt) {
+        eprintln!("Error writing credential file '{}': {}", output_file, e);
+        process::exit(1);
+    }
+
+    println!("✅ Verifiable Credential signed successfully!");
+    println!("📄 Credential: {} ({} bytes)", output_file, jwt.len());
+    println!("🪪 Issuer: {}", issuer);
+}
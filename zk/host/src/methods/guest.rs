@@ -1,8 +1,14 @@
 use risc0_zkvm::sha::Digest;
 
 pub const GUEST_ELF: &[u8] = include_bytes!("../../../target/riscv32im-risc0-zkvm-elf/release/guest");
+pub const AGGREGATOR_ELF: &[u8] =
+    include_bytes!("../../../target/riscv32im-risc0-zkvm-elf/release/aggregator");
 
-// Compute image ID at runtime since it can't be done at compile time
+// Compute image IDs at runtime since they can't be done at compile time
 pub fn guest_id() -> Digest {
     risc0_binfmt::compute_image_id(GUEST_ELF).unwrap()
-}
\ No newline at end of file
+}
+
+pub fn aggregator_id() -> Digest {
+    risc0_binfmt::compute_image_id(AGGREGATOR_ELF).unwrap()
+}
@@ -0,0 +1,79 @@
+use chrono::Utc;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CredentialSubject {
+    #[serde(rename = "programHash")]
+    pub program_hash: String,
+    #[serde(rename = "inputHash")]
+    pub input_hash: String,
+    #[serde(rename = "outputHash")]
+    pub output_hash: String,
+    pub keywords: serde_json::Value,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub credential_type: Vec<String>,
+    pub issuer: String,
+    #[serde(rename = "issuanceDate")]
+    pub issuance_date: String,
+    #[serde(rename = "credentialSubject")]
+    pub credential_subject: CredentialSubject,
+}
+
+#[derive(Serialize, Debug)]
+struct VcClaims {
+    iss: String,
+    iat: i64,
+    vc: VerifiableCredential,
+}
+
+/// Packages a committed journal as a W3C Verifiable Credential and signs it as a compact JWS.
+pub fn sign_journal_as_vc(
+    journal: &serde_json::Value,
+    issuer: &str,
+    algorithm: Algorithm,
+    key_pem: &[u8],
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let credential_subject = CredentialSubject {
+        program_hash: journal["programHash"].as_str().unwrap_or_default().to_string(),
+        input_hash: journal["inputHash"].as_str().unwrap_or_default().to_string(),
+        output_hash: journal["outputHash"].as_str().unwrap_or_default().to_string(),
+        keywords: journal["keywords"].clone(),
+    };
+
+    let vc = VerifiableCredential {
+        context: vec!["https://www.w3.org/2018/credentials/v1".to_string()],
+        credential_type: vec![
+            "VerifiableCredential".to_string(),
+            "SummarizationProofCredential".to_string(),
+        ],
+        issuer: issuer.to_string(),
+        issuance_date: Utc::now().to_rfc3339(),
+        credential_subject,
+    };
+
+    let iat = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let claims = VcClaims {
+        iss: issuer.to_string(),
+        iat,
+        vc,
+    };
+
+    let encoding_key = match algorithm {
+        Algorithm::ES256 => EncodingKey::from_ec_pem(key_pem)?,
+        Algorithm::RS256 => EncodingKey::from_rsa_pem(key_pem)?,
+        other => panic!("unsupported signing algorithm for credentials: {:?}", other),
+    };
+
+    encode(&Header::new(algorithm), &claims, &encoding_key)
+}